@@ -1,4 +1,5 @@
-use lunatic::net::{TcpListener, TcpStream};
+use lunatic::net::TcpStream;
+use lunatic::Mailbox;
 use serde::{Deserialize, Serialize};
 use tungstenite::server;
 use wactor::*;
@@ -9,16 +10,12 @@ enum Message {
     Pong,
 }
 
-struct Server;
-impl Actor for Server {
-    type Input = TcpStream;
-    type Output = ();
-
-    fn create() -> Self {
-        Self
-    }
-
-    fn handle(&mut self, stream: Self::Input, link: &Link<Self>) {
+/// Handles one websocket connection: skip messages until the first binary one, reply to that with
+/// its opposite, done. Runs
+/// on its own process per connection, spawned by `ConnectionServer`.
+struct WsHandler;
+impl ConnectionHandler for WsHandler {
+    fn handle(stream: TcpStream) {
         let mut ws = server::accept(stream).unwrap();
         loop {
             let msg = ws.read_message();
@@ -34,49 +31,27 @@ impl Actor for Server {
                     let buf = bincode::serialize(&response).unwrap();
                     ws.write_message(buf.into()).unwrap();
 
-                    // Exit loop when after a successful response
+                    // Exit loop after a successful response
                     break;
                 }
                 Err(_) => break,
                 _ => {}
             }
         }
-        // Signal this is actor is ready to be dropped.
-        link.close();
     }
 }
 
-struct Listener;
-impl Actor for Listener {
-    type Input = String;
-    type Output = ();
-
-    fn create() -> Self {
-        Self
-    }
-
-    fn handle(&mut self, addr: Self::Input, link: &Link<Self>) {
-        let listener = TcpListener::bind(&addr).expect("Failed to bind");
-        // Notify we're ready to accept connections.
-        link.respond(()).unwrap();
-        loop {
-            if let Ok(stream) = listener.accept() {
-                // Spawn a server for this connection
-                wactor::spawn::<Server>().send(stream).unwrap()
-            }
-        }
-    }
-}
-
-fn main() {
+#[lunatic::main]
+fn main(_m: Mailbox<()>) {
     let server_url = "127.0.0.1:6000";
-    // Start listening
-    wactor::spawn::<Listener>()
-        // Wait until its ready
-        .get(server_url)
-        .unwrap();
+    // Bind and spawn a server that hands each connection to `WsHandler` on its own process.
+    let server = wactor::spawn_connection_server::<WsHandler>(server_url, 16).unwrap();
+    // Start accepting. Fire-and-forget: the server keeps re-sending `Run` to itself, and the reply
+    // to this first one (sent once its single accept step finishes) isn't waited for.
+    server.send(ConnectionMessage::Run).ok();
 
-    // Connect to our server.
+    // Connect to our server. Binding already happened above, so this can't race the accept loop
+    // starting.
     let client = TcpStream::connect(server_url).unwrap();
     let (mut socket, _response) =
         tungstenite::client(format!("ws://{}", server_url), client).expect("Failed to connect");
@@ -96,4 +71,7 @@ fn main() {
         }
     }
     println!("Done");
+
+    // Stop accepting new connections before exiting.
+    server.send(ConnectionMessage::Shutdown).ok();
 }
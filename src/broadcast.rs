@@ -0,0 +1,109 @@
+//! Fan-out from one [`Actor`] to many subscribers. The core request/reply model gives exactly one
+//! reply to whoever sent the current request; a [`Broadcaster`] instead holds a set of
+//! subscribers and pushes every published value to all of them, for event-stream use cases (e.g.
+//! notifying every connected client whenever something happens).
+use lunatic::process::Process;
+use lunatic::Mailbox;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{Actor, Bridge, Link, SendError};
+
+/// Control messages understood by [`Broadcaster`].
+#[derive(Serialize, Deserialize)]
+pub enum Message<T> {
+    /// Register `subscriber` to receive every value published from now on.
+    Subscribe(Process<T>),
+    /// Push `T` to every current subscriber.
+    Publish(T),
+}
+
+/// An actor that fans a published value out to every subscriber that joined via
+/// [`Message::Subscribe`]. Subscribers whose mailbox has died are pruned the next time something
+/// is published.
+pub struct Broadcaster<T> {
+    subscribers: Vec<Process<T>>,
+}
+
+impl<T: Serialize + DeserializeOwned + Clone> Actor for Broadcaster<T> {
+    type Input = Message<T>;
+    type Output = ();
+    type Context = ();
+
+    fn create(_ctx: Self::Context) -> Self {
+        Self {
+            subscribers: Vec::new(),
+        }
+    }
+
+    fn handle(&mut self, msg: &Self::Input, _link: &Link<Self>) -> Self::Output {
+        match msg {
+            Message::Subscribe(subscriber) => self.subscribers.push(subscriber.clone()),
+            Message::Publish(value) => self
+                .subscribers
+                .retain(|subscriber| subscriber.send(value.clone()).is_ok()),
+        }
+    }
+}
+
+impl<T: Serialize + DeserializeOwned + Clone> Broadcaster<T> {
+    /// Register `subscriber` to receive every value published to `broadcaster` from now on.
+    ///
+    /// `subscriber` is a raw `Process<T>`, not a [`Bridge`] — no [`Actor`] built with this crate
+    /// holds one of those directly, since a `Bridge<A>` is a `Process<Request<A::Input,
+    /// A::Output>>`, not a `Process<T>`. To subscribe an actor, spawn a small forwarding process
+    /// with [`forward_to`] and subscribe that instead; see that function for a worked example.
+    pub fn subscribe(
+        broadcaster: &Bridge<Self>,
+        subscriber: Process<T>,
+    ) -> Result<(), SendError<Process<T>>> {
+        // `Message<T>` isn't `Clone` (a `Process<T>` subscriber is cheap to clone, but the `T`
+        // carried by a `Publish` need not be), so this goes through lunatic's inherent `send`
+        // directly instead of the crate's `send`/`request` helpers, the same way
+        // `Link::publish` does.
+        broadcaster
+            .send(Message::Subscribe(subscriber.clone()))
+            .map_err(|_| SendError::new(subscriber))
+    }
+}
+
+/// Spawns a process that receives `T` on a plain mailbox and re-sends each one into `bridge` as an
+/// `A::Input`, then returns that process's handle so it can be registered with
+/// [`Broadcaster::subscribe`]. This is how an actor built with this crate becomes a subscriber:
+/// its own [`Bridge`] can't be handed to `subscribe` directly (see there), so `forward_to` stands
+/// in for it.
+///
+/// ```no_run
+/// # use serde::{Deserialize, Serialize};
+/// # use wactor::*;
+/// # #[derive(Serialize, Deserialize, Clone)]
+/// # struct Event;
+/// # struct Chat;
+/// # #[derive(Serialize, Deserialize, Clone)]
+/// # enum ChatInput { Event(Event) }
+/// # impl Actor for Chat {
+/// #     type Input = ChatInput;
+/// #     type Output = ();
+/// #     type Context = ();
+/// #     fn create(_ctx: ()) -> Self { Self }
+/// #     fn handle(&mut self, _msg: &Self::Input, _link: &Link<Self>) {}
+/// # }
+/// let broadcaster = wactor::spawn::<Broadcaster<Event>>().unwrap();
+/// let chat = wactor::spawn::<Chat>().unwrap();
+/// let forwarder = forward_to(chat, ChatInput::Event).unwrap();
+/// Broadcaster::subscribe(&broadcaster, forwarder).unwrap();
+/// ```
+pub fn forward_to<A: Actor, T: Serialize + DeserializeOwned + 'static>(
+    bridge: Bridge<A>,
+    mut into_input: impl FnMut(T) -> A::Input + 'static,
+) -> Result<Process<T>, lunatic::LunaticError>
+where
+    A::Input: Clone,
+{
+    lunatic::process::spawn_with(bridge, move |bridge, mailbox: Mailbox<T>| {
+        while let Ok(value) = mailbox.receive() {
+            if crate::send(&bridge, into_input(value)).is_err() {
+                break;
+            }
+        }
+    })
+}
@@ -0,0 +1,146 @@
+//! Generalizes the hand-rolled `Listener`/`Server` pair from the websocket example into a reusable
+//! subsystem: bind once, then let the crate own the accept loop, readiness, graceful shutdown,
+//! and a cap on how many connections are served at once.
+use std::fmt;
+use std::time::Duration;
+
+use lunatic::net::{TcpListener, TcpStream};
+use lunatic::process;
+use lunatic::{LunaticError, Mailbox};
+use serde::{Deserialize, Serialize};
+
+use crate::{spawn_with, Actor, Bridge, Link};
+
+/// Binding the listener or spawning the server process failed.
+#[derive(Debug)]
+pub enum SpawnError {
+    /// `TcpListener::bind` failed.
+    Bind(std::io::Error),
+    /// Spawning the server's process failed.
+    Spawn(LunaticError),
+}
+
+impl fmt::Display for SpawnError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SpawnError::Bind(err) => write!(f, "failed to bind: {err}"),
+            SpawnError::Spawn(err) => write!(f, "failed to spawn connection server: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for SpawnError {}
+
+/// How long a single accept attempt waits for an incoming connection before [`Message::Run`]
+/// gives back control, letting other queued messages (a `Shutdown`, or a `Finished` from a
+/// connection that just completed) get their turn.
+const POLL: Duration = Duration::from_millis(50);
+
+/// Serves each connection a [`ConnectionServer`] accepts, on a process the server spawns for it.
+pub trait ConnectionHandler {
+    /// Handle a single accepted connection. Runs on its own process, so a panic here is isolated
+    /// to this connection and doesn't affect the server or any other connection.
+    fn handle(stream: TcpStream);
+}
+
+/// Control messages understood by a [`ConnectionServer`].
+#[derive(Serialize, Deserialize)]
+pub enum Message {
+    /// Accept at most one connection (or wait up to [`POLL`] for one to show up), then re-send
+    /// itself to keep accepting — same one-message-in-one-reply-out shape as every other actor's
+    /// `handle`, just chained instead of answering once at the very end. Send this once to start
+    /// the server; each reply arrives as soon as that single step finishes, not once the server
+    /// stops (that's what [`Message::Shutdown`]'s reply is for).
+    Run,
+    /// Stop accepting new connections: the next [`Message::Run`] step sees this and doesn't
+    /// re-send itself. Already-spawned handlers keep running to completion. The reply arrives as
+    /// soon as the flag is set, not once the accept chain has actually wound down.
+    Shutdown,
+    // Sent by a connection's own process once `H::handle` returns, so the server's concurrency
+    // counter reflects connections that finished, not just ones that started.
+    Finished,
+}
+
+/// Binds `addr` and returns a [`Bridge`] to a [`ConnectionServer`] ready to accept up to
+/// `max_connections` connections at a time, each served by `H::handle` on its own process.
+///
+/// Binding happens synchronously in the caller, so a failure to bind is returned directly instead
+/// of needing a readiness round-trip once the server is already running.
+pub fn spawn<H: ConnectionHandler>(
+    addr: &str,
+    max_connections: usize,
+) -> Result<Bridge<ConnectionServer<H>>, SpawnError> {
+    let listener = TcpListener::bind(addr).map_err(SpawnError::Bind)?;
+    spawn_with::<ConnectionServer<H>>((listener, max_connections)).map_err(SpawnError::Spawn)
+}
+
+/// Owns a bound [`TcpListener`] and spawns a fresh process running `H::handle` for each accepted
+/// connection, pausing acceptance once `max_connections` are outstanding.
+pub struct ConnectionServer<H: ConnectionHandler> {
+    listener: TcpListener,
+    max_connections: usize,
+    active: usize,
+    shutting_down: bool,
+    _handler: std::marker::PhantomData<H>,
+}
+
+impl<H: ConnectionHandler> Actor for ConnectionServer<H> {
+    type Input = Message;
+    type Output = ();
+    type Context = (TcpListener, usize);
+
+    fn create((listener, max_connections): Self::Context) -> Self {
+        Self {
+            listener,
+            max_connections,
+            active: 0,
+            shutting_down: false,
+            _handler: std::marker::PhantomData,
+        }
+    }
+
+    fn handle(&mut self, msg: &Self::Input, _link: &Link<Self>) -> Self::Output {
+        self.apply(msg);
+
+        if matches!(msg, Message::Run) && !self.shutting_down {
+            // This process's own bridge: handed to an accepted connection so it can report back
+            // when `H::handle` returns, and used below to keep the accept chain going.
+            let this: Bridge<Self> = process::this();
+
+            if self.active < self.max_connections {
+                // Bounded wait so this step returns promptly even when no connection shows up,
+                // instead of blocking the whole actor on the next accept.
+                if let Ok(stream) = self.listener.accept_timeout(POLL) {
+                    process::spawn_with(
+                        (stream, this.clone()),
+                        |(stream, server), _: Mailbox<()>| {
+                            H::handle(stream);
+                            server.send(Message::Finished).ok();
+                        },
+                    )
+                    .ok();
+                    self.active += 1;
+                }
+            } else {
+                // Saturated: skip this step's accept and just wait for a slot to free up.
+                std::thread::sleep(POLL);
+            }
+
+            // Re-send `Run` to keep the accept chain going. Any `Shutdown`/`Finished` sent while
+            // this step was running is already queued ahead of it in the mailbox, and anything
+            // sent later waits behind at most one step, so they get their turn within one `POLL`
+            // instead of being starved by a tight self-send loop.
+            this.send(Message::Run).ok();
+        }
+    }
+}
+
+impl<H: ConnectionHandler> ConnectionServer<H> {
+    fn apply(&mut self, msg: &Message) {
+        match msg {
+            Message::Shutdown => self.shutting_down = true,
+            Message::Finished => self.active = self.active.saturating_sub(1),
+            Message::Run => {}
+        }
+    }
+}
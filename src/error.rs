@@ -0,0 +1,122 @@
+//! Typed alternatives to the opaque errors lunatic surfaces when the other side of a [`Bridge`]
+//! has died, modeled on the `std::sync::mpsc` send/recv error split.
+use std::fmt;
+use std::time::Duration;
+
+use lunatic::ReceiveError;
+
+use crate::{Actor, Bridge};
+
+/// `input` could not be delivered because the actor behind the [`Bridge`] is no longer running.
+/// Unlike a bare lunatic error, this owns the message so the caller can retry, reroute it to
+/// another worker, or log it instead of losing it.
+pub struct SendError<T>(T);
+
+impl<T> SendError<T> {
+    /// Recover the message that failed to send.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+
+    /// Build a `SendError` directly from the message that failed to send. For callers sending on
+    /// a [`Bridge`] through lunatic's inherent `send`/`request` instead of the [`send`]/[`request`]
+    /// helpers below — e.g. because the message they're holding isn't `A::Input` — and so can't
+    /// get a `SendError` from those helpers' `Clone`-based retry.
+    pub(crate) fn new(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T> fmt::Debug for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("SendError").finish()
+    }
+}
+
+impl<T> fmt::Display for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("sending on a closed actor")
+    }
+}
+
+impl<T> std::error::Error for SendError<T> {}
+
+/// No request could be received. Used where, unlike [`request`]/[`request_timeout`], there was no
+/// message of our own to hand back — e.g. [`Link::receive_timeout`](crate::Link::receive_timeout)
+/// waiting on the *next* incoming request, rather than a reply to one we sent. It covers nothing
+/// arriving before the deadline, the link already being closed, and the receive itself failing;
+/// these cases are not told apart.
+#[derive(Debug)]
+pub struct RecvError;
+
+impl fmt::Display for RecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("no request received")
+    }
+}
+
+impl std::error::Error for RecvError {}
+
+/// Send `input` to `bridge` without waiting for a reply, returning the message back via
+/// [`SendError`] if the actor has died.
+pub fn send<A: Actor>(bridge: &Bridge<A>, input: A::Input) -> Result<(), SendError<A::Input>>
+where
+    A::Input: Clone,
+{
+    bridge.send(input.clone()).map_err(|_| SendError(input))
+}
+
+/// Send `input` to `bridge` and block for its reply, returning the message back via [`SendError`]
+/// if the actor died, whether that happened before `input` was delivered or while it was being
+/// handled. lunatic doesn't distinguish those two cases to the caller of a single `request` call,
+/// so neither do we — but either way we still have our own clone of `input` to hand back, which is
+/// strictly more useful than losing it.
+pub fn request<A: Actor>(
+    bridge: &Bridge<A>,
+    input: A::Input,
+) -> Result<A::Output, RequestError<A::Input>>
+where
+    A::Input: Clone,
+{
+    bridge
+        .request(input.clone())
+        .map_err(|_| RequestError::Send(SendError(input)))
+}
+
+/// A [`request`]/[`request_timeout`] call can fail by the actor dying, or by the deadline passing.
+#[derive(Debug)]
+pub enum RequestError<T> {
+    /// The actor died; see [`SendError`].
+    Send(SendError<T>),
+    /// The actor didn't reply before the deadline passed; see [`request_timeout`].
+    Timeout,
+}
+
+impl<T> fmt::Display for RequestError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RequestError::Send(err) => err.fmt(f),
+            RequestError::Timeout => f.write_str("timed out waiting on a request"),
+        }
+    }
+}
+
+impl<T> std::error::Error for RequestError<T> {}
+
+/// Like [`request`], but gives up and returns [`RequestError::Timeout`] instead of blocking
+/// indefinitely if the actor hasn't replied after `timeout`. The [`Bridge`] remains usable
+/// afterward, so callers can retry, back off, or treat the actor as wedged.
+pub fn request_timeout<A: Actor>(
+    bridge: &Bridge<A>,
+    input: A::Input,
+    timeout: Duration,
+) -> Result<A::Output, RequestError<A::Input>>
+where
+    A::Input: Clone,
+{
+    match bridge.request_timeout(input.clone(), timeout) {
+        Ok(output) => Ok(output),
+        Err(ReceiveError::Timeout) => Err(RequestError::Timeout),
+        Err(_) => Err(RequestError::Send(SendError(input))),
+    }
+}
@@ -62,6 +62,7 @@
 //!     cargo build --release --target=wasm32-wasi --example basic
 //!     lunatic target/wasm32-wasi/release/examples/basic.wasm
 use std::cell::Cell;
+use std::time::Duration;
 
 use lunatic::{
     process::{self, Process},
@@ -69,6 +70,27 @@ use lunatic::{
 };
 use serde::{de::DeserializeOwned, Serialize};
 
+mod broadcast;
+mod connection;
+mod error;
+mod pool;
+mod supervisor;
+pub use broadcast::{forward_to, Broadcaster, Message as BroadcastMessage};
+pub use connection::{
+    spawn as spawn_connection_server, ConnectionHandler, ConnectionServer,
+    Message as ConnectionMessage, SpawnError as ConnectionSpawnError,
+};
+pub use error::{request, request_timeout, send, RecvError, RequestError, SendError};
+pub use pool::{spawn_pool, spawn_pool_with, Pool, PoolSpawnError};
+pub use supervisor::{spawn_supervised, RestartStrategy};
+
+/// How long [`Link::receive`] waits for a queued message while [draining](Link::close_draining)
+/// before concluding the mailbox is empty and closing for good. Draining is a rare, not
+/// latency-sensitive path, so this is long enough to give a send that raced `close_draining` a
+/// real chance to land, rather than a zero-wait poll that reads "nothing queued yet" as "nothing
+/// ever coming" and drops exactly the in-flight request this feature exists to flush.
+const DRAIN_TIMEOUT: Duration = Duration::from_millis(5);
+
 /// Actors run on isolated green threads. The cannot share memory, and communicate only through
 /// input and output messages. Consequently messages must be serialized to travel between threads.
 pub trait Actor: Sized {
@@ -82,56 +104,116 @@ pub trait Actor: Sized {
     fn handle(&mut self, msg: &Self::Input, link: &Link<Self>) -> Self::Output;
 }
 
+/// Handle for sending requests to an [Actor] and receiving its replies. Cloning a `Bridge` gives
+/// another handle to the same actor; the actor is dropped once every bridge to it has been
+/// dropped.
+pub type Bridge<A> = Process<Request<<A as Actor>::Input, <A as Actor>::Output>>;
+
 /// Spawn a new [Actor], returning its [Bridge]. Actor is dropped when all bridges have been
 /// dropped.
-pub fn spawn_with<A: Actor>(
-    ctx: A::Context,
-) -> Result<Process<Request<<A as Actor>::Input, <A as Actor>::Output>>, LunaticError> {
-    process::spawn_with(
-        ctx,
-        |ctx, mailbox: Mailbox<Request<A::Input, A::Output>>| {
-            Context {
-                link: Link {
-                    mailbox,
-                    open: Cell::new(true),
-                },
-                actor: A::create(ctx),
-            }
-            .run()
-        },
-    )
+pub fn spawn_with<A: Actor>(ctx: A::Context) -> Result<Bridge<A>, LunaticError> {
+    process::spawn_with(ctx, run_actor::<A>)
 }
 
-pub fn spawn<A: Actor<Context = ()>>(
-) -> Result<Process<Request<<A as Actor>::Input, <A as Actor>::Output>>, LunaticError> {
+pub fn spawn<A: Actor<Context = ()>>() -> Result<Bridge<A>, LunaticError> {
     spawn_with::<A>(())
 }
 
-enum LinkError {
+/// Like [`spawn_with`], but links the new process to the caller the way [`spawn_supervised`]
+/// needs to: an abnormal exit of a linked process reaches the caller (which dies with it unless
+/// it traps link deaths, and then receives the exit as an event it can react to), whereas an
+/// ordinary (unlinked) process dies without the caller hearing of it.
+///
+/// [`spawn_supervised`]: crate::spawn_supervised
+pub(crate) fn spawn_link_with<A: Actor>(ctx: A::Context) -> Result<Bridge<A>, LunaticError> {
+    process::spawn_link_with(ctx, run_actor::<A>)
+}
+
+fn run_actor<A: Actor>(ctx: A::Context, mailbox: Mailbox<Request<A::Input, A::Output>>) {
+    Context {
+        link: Link {
+            mailbox,
+            state: Cell::new(State::Open),
+        },
+        actor: A::create(ctx),
+    }
+    .run()
+}
+
+pub(crate) enum LinkError {
     Receive(ReceiveError),
     Closed,
 }
 
+// Whether a [`Link`] may still receive messages.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum State {
+    /// Accepting new requests as normal.
+    Open,
+    /// No longer accepting new requests, but still flushing whatever is already queued in the
+    /// mailbox.
+    Draining,
+    /// Dropped: the actor exits after handling its current message, if any.
+    Closed,
+}
+
 /// Link for responding to input messages.
 pub struct Link<A: Actor> {
-    mailbox: Mailbox<Request<A::Input, A::Output>>,
-    // Whether this link may receive messages. Setting this to true will drop actor after it's done
-    // handling current message.
-    open: Cell<bool>,
+    pub(crate) mailbox: Mailbox<Request<A::Input, A::Output>>,
+    pub(crate) state: Cell<State>,
 }
 
 impl<A: Actor> Link<A> {
-    /// Signal this actor should be dropped after handling current message.
+    /// Signal this actor should be dropped after handling the current message. Anything still
+    /// queued in the mailbox is dropped without a reply.
     pub fn close(&self) {
-        self.open.set(false);
+        self.state.set(State::Closed);
+    }
+
+    /// Signal this actor should stop accepting new requests, but keep handling whatever is
+    /// already queued in the mailbox before exiting. Unlike [`close`](Self::close), queued
+    /// requests still get a reply.
+    pub fn close_draining(&self) {
+        if self.state.get() == State::Open {
+            self.state.set(State::Draining);
+        }
+    }
+
+    /// Publish `value` to every subscriber of `broadcaster`, in addition to whatever this actor
+    /// replies to the current request with. Delivery to a dead subscriber is ignored; it's pruned
+    /// by `broadcaster` on its next publish.
+    pub fn publish<T: Serialize + DeserializeOwned + Clone>(
+        &self,
+        broadcaster: &Bridge<Broadcaster<T>>,
+        value: T,
+    ) {
+        broadcaster.send(BroadcastMessage::Publish(value)).ok();
+    }
+
+    pub(crate) fn receive(&self) -> Result<Request<A::Input, A::Output>, LinkError> {
+        match self.state.get() {
+            State::Closed => Err(LinkError::Closed),
+            State::Open => self.mailbox.receive().map_err(LinkError::Receive),
+            State::Draining => self.mailbox.receive_timeout(DRAIN_TIMEOUT).map_err(|_| {
+                // Nothing left queued (or the wait itself failed): finish draining for good.
+                self.state.set(State::Closed);
+                LinkError::Closed
+            }),
+        }
     }
 
-    fn receive(&self) -> Result<Request<A::Input, A::Output>, LinkError> {
-        if !self.open.get() {
-            return Err(LinkError::Closed);
+    /// Wait for the next request, giving up with [`RecvError`] instead of blocking indefinitely
+    /// if none arrives before `timeout`. A low-level escape hatch for custom receive loops that
+    /// want the same bounded wait [`request_timeout`] gives callers.
+    pub fn receive_timeout(
+        &self,
+        timeout: Duration,
+    ) -> Result<Request<A::Input, A::Output>, RecvError> {
+        if self.state.get() == State::Closed {
+            return Err(RecvError);
         }
 
-        self.mailbox.receive().map_err(LinkError::Receive)
+        self.mailbox.receive_timeout(timeout).map_err(|_| RecvError)
     }
 }
 
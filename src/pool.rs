@@ -0,0 +1,95 @@
+//! A fixed set of identical actor processes fed in round-robin order, so stateless `handle` work
+//! can be spread across lunatic's green threads instead of serializing through one actor.
+use std::cell::Cell;
+use std::fmt;
+use std::rc::Rc;
+
+use lunatic::LunaticError;
+
+use crate::{spawn_with, Actor, Bridge, RequestError, SendError};
+
+/// `spawn_pool`/`spawn_pool_with` failed.
+#[derive(Debug)]
+pub enum PoolSpawnError {
+    /// A pool needs at least one worker; `n == 0` would leave [`Pool::send`]/[`Pool::request`]
+    /// with no worker to pick and nothing to do but panic.
+    Empty,
+    /// Spawning one of the pool's workers failed.
+    Spawn(LunaticError),
+}
+
+impl fmt::Display for PoolSpawnError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PoolSpawnError::Empty => f.write_str("a pool needs at least one worker"),
+            PoolSpawnError::Spawn(err) => write!(f, "failed to spawn pool worker: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for PoolSpawnError {}
+
+/// A round-robin pool of `n` independent [Actor] processes sharing one logical address. Cloning a
+/// `Pool` gives another handle to the same workers and round-robin position.
+pub struct Pool<A: Actor> {
+    workers: Rc<[Bridge<A>]>,
+    next: Rc<Cell<usize>>,
+}
+
+impl<A: Actor> Clone for Pool<A> {
+    fn clone(&self) -> Self {
+        Self {
+            workers: self.workers.clone(),
+            next: self.next.clone(),
+        }
+    }
+}
+
+/// Spawn a [Pool] of `n` actors, each created from the context `make_ctx` returns for its index.
+/// `n` must be at least 1.
+pub fn spawn_pool_with<A: Actor>(
+    n: usize,
+    mut make_ctx: impl FnMut(usize) -> A::Context,
+) -> Result<Pool<A>, PoolSpawnError> {
+    if n == 0 {
+        return Err(PoolSpawnError::Empty);
+    }
+
+    let workers = (0..n)
+        .map(|i| spawn_with::<A>(make_ctx(i)))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(PoolSpawnError::Spawn)?;
+    Ok(Pool {
+        workers: workers.into(),
+        next: Rc::new(Cell::new(0)),
+    })
+}
+
+/// Spawn a [Pool] of `n` actors that don't need a context. `n` must be at least 1.
+pub fn spawn_pool<A: Actor<Context = ()>>(n: usize) -> Result<Pool<A>, PoolSpawnError> {
+    spawn_pool_with::<A>(n, |_| ())
+}
+
+impl<A: Actor> Pool<A>
+where
+    A::Input: Clone,
+{
+    /// Send `input` to the next worker in round-robin order without waiting for a reply. Returns
+    /// the message back via [`SendError`] if that worker has died.
+    pub fn send(&self, input: A::Input) -> Result<(), SendError<A::Input>> {
+        crate::send(self.next_worker(), input)
+    }
+
+    /// Send `input` to the next worker in round-robin order and block for its reply.
+    pub fn request(&self, input: A::Input) -> Result<A::Output, RequestError<A::Input>> {
+        crate::request(self.next_worker(), input)
+    }
+}
+
+impl<A: Actor> Pool<A> {
+    fn next_worker(&self) -> &Bridge<A> {
+        let i = self.next.get();
+        self.next.set((i + 1) % self.workers.len());
+        &self.workers[i]
+    }
+}
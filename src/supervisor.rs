@@ -0,0 +1,129 @@
+//! Turns an actor dying from a fatal, unrecoverable failure into a recoverable fault:
+//! [`spawn_supervised`] runs `A` as its own lunatic-linked child process and restarts it from its
+//! original [`Context`](Actor::Context) whenever that link reports the child died, instead of
+//! letting every [`Bridge`] to it start returning errors forever.
+use std::time::{Duration, Instant};
+
+use lunatic::{process, LinkTrapped, LunaticError, Mailbox, Request};
+
+use crate::{spawn_link_with, Actor, Bridge};
+
+/// How a supervised actor is restarted after it dies.
+pub enum RestartStrategy {
+    /// Restart only the actor that died, re-creating it from its original context.
+    ///
+    /// If more than `max_restarts` restarts happen inside the sliding `window`, the supervisor
+    /// gives up: the actor is left dead and its bridge starts returning errors, same as an
+    /// unsupervised actor that died.
+    OneForOne {
+        /// Restarts allowed within `window` before the supervisor gives up.
+        max_restarts: usize,
+        /// Width of the sliding window restarts are counted against.
+        window: Duration,
+        /// Delay inserted before each restart, doubling after every consecutive one.
+        backoff: Option<Duration>,
+    },
+}
+
+/// Spawn `A` behind a supervisor implementing `strategy`, returning a [`Bridge`] that stays valid
+/// across restarts: `A` runs as its own process, linked to the supervisor, so the supervisor
+/// learns of — and survives — anything that takes the child down: not just an unwinding panic,
+/// but a stack overflow, a trap, or `process::kill`, the same abnormal exits a process link
+/// catches for any other lunatic process. Once the child is back up, later requests get a normal
+/// reply again. Requests are relayed to the child as soon as they arrive, so every request the
+/// dead child had already been handed but not yet answered is not replied to (there's no `Output`
+/// to send for it); the callers see those specific calls fail, same as if the actor had died
+/// unsupervised.
+pub fn spawn_supervised<A: Actor>(
+    ctx: A::Context,
+    strategy: RestartStrategy,
+) -> Result<Bridge<A>, LunaticError>
+where
+    A::Context: Clone,
+{
+    process::spawn_with(
+        (ctx, strategy),
+        |(ctx, strategy), mailbox: Mailbox<Request<A::Input, A::Output>>| {
+            Supervisor::<A> {
+                ctx,
+                strategy,
+                restarts: Vec::new(),
+            }
+            .run(mailbox)
+        },
+    )
+}
+
+struct Supervisor<A: Actor> {
+    ctx: A::Context,
+    strategy: RestartStrategy,
+    // Timestamps of restarts still inside the current window, oldest first.
+    restarts: Vec<Instant>,
+}
+
+impl<A: Actor> Supervisor<A>
+where
+    A::Context: Clone,
+{
+    fn run(mut self, mailbox: Mailbox<Request<A::Input, A::Output>>) {
+        // Without this, a linked process dying would take the supervisor down with it — which is
+        // exactly what supervision exists to avoid. Trapping it instead turns that death into a `LinkTrapped`
+        // value the supervisor can react to on its next receive.
+        let mailbox = mailbox.catch_link_panic();
+
+        let Ok(mut child) = spawn_link_with::<A>(self.ctx.clone()) else {
+            // Couldn't even get the first child up; nothing left to supervise. The bridge we
+            // already handed back to the caller starts failing immediately, same as any other
+            // actor that died before handling its first request.
+            return;
+        };
+
+        loop {
+            match mailbox.receive() {
+                // A genuine request from one of our bridges: hand it straight to the current
+                // child so it replies directly to the original caller: the supervisor is just
+                // relaying, not a second hop in the reply path.
+                Ok(LinkTrapped::Message(request)) => {
+                    child.send(request).ok();
+                }
+                // The child we're linked to died. Restart it from the same context, or give up
+                // and let this supervisor (and the bridge to it) die too once out of budget.
+                Ok(LinkTrapped::Died(_)) => {
+                    if !self.record_restart() {
+                        break;
+                    }
+                    match spawn_link_with::<A>(self.ctx.clone()) {
+                        Ok(restarted) => child = restarted,
+                        Err(_) => break,
+                    }
+                }
+                // Every bridge to us has been dropped.
+                Err(_) => break,
+            }
+        }
+    }
+
+    // Records a restart against the sliding window, returning whether the supervisor is still
+    // within its restart budget. Sleeps for the configured backoff as a side effect when it is.
+    fn record_restart(&mut self) -> bool {
+        let RestartStrategy::OneForOne {
+            max_restarts,
+            window,
+            backoff,
+        } = &self.strategy;
+
+        let now = Instant::now();
+        self.restarts
+            .retain(|restarted_at| now.duration_since(*restarted_at) < *window);
+        if self.restarts.len() >= *max_restarts {
+            return false;
+        }
+        self.restarts.push(now);
+
+        if let Some(backoff) = backoff {
+            let exponent = self.restarts.len() as u32 - 1;
+            std::thread::sleep(*backoff * 2u32.pow(exponent));
+        }
+        true
+    }
+}